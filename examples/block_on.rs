@@ -1,35 +1,49 @@
 use std::{
     future::Future,
     pin::Pin,
+    sync::{Arc, Condvar, Mutex},
     task::{Context, Poll},
-    thread,
 };
 use wakeful::Wake;
 
 /// Block the current thread until this future is ready.
 pub fn block_on<F: Future>(mut future: F) -> F::Output {
-    /// Note that this crate already implements `Wake` for `Thread`, this just
-    /// demonstrates how simple the implementation is.
-    #[derive(Clone)]
-    struct ThreadWaker(thread::Thread);
+    /// Wakes via an explicit notification flag rather than
+    /// `thread::park`/`unpark`, so nested calls to `block_on` on the same
+    /// thread can't steal each other's wakeups.
+    #[derive(Clone, Default)]
+    struct Notify(Arc<(Mutex<bool>, Condvar)>);
 
-    impl Wake for ThreadWaker {
+    impl Notify {
+        fn wait(&self) {
+            let (lock, condvar) = &*self.0;
+            let mut woken = lock.lock().unwrap();
+
+            while !*woken {
+                woken = condvar.wait(woken).unwrap();
+            }
+
+            *woken = false;
+        }
+    }
+
+    impl Wake for Notify {
         fn wake_by_ref(&self) {
-            self.0.unpark();
+            let (lock, condvar) = &*self.0;
+            *lock.lock().unwrap() = true;
+            condvar.notify_one();
         }
     }
 
-    // Now that we can easily create a waker that does what we want (unpark this
-    // thread), it is now easy to create a context and begin polling the given
-    // future efficiently.
-    let waker = ThreadWaker(thread::current()).into_waker();
+    let notify = Notify::default();
+    let waker = notify.clone().into_waker();
     let mut context = Context::from_waker(&waker);
     let mut future = unsafe { Pin::new_unchecked(&mut future) };
 
     loop {
         match future.as_mut().poll(&mut context) {
             Poll::Ready(output) => return output,
-            Poll::Pending => thread::park(),
+            Poll::Pending => notify.wait(),
         }
     }
 }
@@ -1,11 +1,55 @@
 use crate::wakers::Wake;
+#[cfg(feature = "nightly")]
+use crate::{context::ContextBuilder, wakers::LocalWake};
 use std::{
     future::Future,
     pin::Pin,
+    sync::{Arc, Condvar, Mutex},
     task::{Context, Poll},
-    thread,
 };
 
+/// A waker that wakes a waiting thread via an explicit notification flag
+/// rather than `thread::park`/`unpark` token semantics.
+///
+/// Each instance owns its own flag, so nested [`Blocking::blocking_wait`]
+/// calls on the same thread can't steal each other's wakeups the way
+/// `park`/`unpark` can, since `unpark` sets a single per-thread token shared
+/// by every caller. Setting the flag before signaling the condvar also
+/// closes the race where a wake fires between `poll` returning `Pending` and
+/// the wait beginning: if the flag is already `true` by the time we go to
+/// wait, we don't wait at all.
+#[derive(Clone, Default)]
+struct Notify(Arc<(Mutex<bool>, Condvar)>);
+
+impl Notify {
+    /// Block until this notify has been woken, then reset it for reuse.
+    fn wait(&self) {
+        let (lock, condvar) = &*self.0;
+        let mut woken = lock.lock().unwrap();
+
+        while !*woken {
+            woken = condvar.wait(woken).unwrap();
+        }
+
+        *woken = false;
+    }
+}
+
+impl Wake for Notify {
+    fn wake_by_ref(&self) {
+        let (lock, condvar) = &*self.0;
+        *lock.lock().unwrap() = true;
+        condvar.notify_one();
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl LocalWake for Notify {
+    fn wake_by_ref(&self) {
+        Wake::wake_by_ref(self);
+    }
+}
+
 /// Extension trait that provides methods for blocking synchronously on a
 /// future.
 pub trait Blocking: Future {
@@ -16,14 +60,45 @@ pub trait Blocking: Future {
     where
         Self: Sized,
     {
-        let waker = thread::current().into_waker();
+        let notify = Notify::default();
+        let waker = notify.clone().into_waker();
         let mut context = Context::from_waker(&waker);
         let mut future = unsafe { Pin::new_unchecked(&mut self) };
 
         loop {
             match future.as_mut().poll(&mut context) {
                 Poll::Ready(output) => return output,
-                Poll::Pending => thread::park(),
+                Poll::Pending => notify.wait(),
+            }
+        }
+    }
+
+    /// Block the current thread until this future is ready, also attaching a
+    /// local waker to the context.
+    ///
+    /// This is for futures that may poll [`Context::local_waker`] internally,
+    /// for example ones that hand off work to an inner `!Send` executor. Both
+    /// the regular [`Waker`](std::task::Waker) and the local waker wake the
+    /// same underlying notification, so either path works.
+    ///
+    /// It is not advised to use this inside an async context.
+    #[cfg(feature = "nightly")]
+    fn blocking_wait_with_local(mut self) -> Self::Output
+    where
+        Self: Sized,
+    {
+        let notify = Notify::default();
+        let waker = notify.clone().into_waker();
+        let local_waker = notify.clone().into_local_waker();
+        let mut context = ContextBuilder::from_waker(&waker)
+            .local_waker(&local_waker)
+            .build();
+        let mut future = unsafe { Pin::new_unchecked(&mut self) };
+
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => notify.wait(),
             }
         }
     }
@@ -43,4 +118,27 @@ mod tests {
 
         assert_eq!(number_async().blocking_wait(), 42);
     }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn blocking_wait_with_local() {
+        async fn number_async() -> usize {
+            42
+        }
+
+        assert_eq!(number_async().blocking_wait_with_local(), 42);
+    }
+
+    #[test]
+    fn nested_blocking_wait_does_not_steal_wakeups() {
+        async fn number_async() -> usize {
+            async fn inner() -> usize {
+                21
+            }
+
+            inner().blocking_wait() * 2
+        }
+
+        assert_eq!(number_async().blocking_wait(), 42);
+    }
 }
@@ -0,0 +1,52 @@
+use std::{
+    fmt,
+    task::{Context, LocalWaker, Waker},
+};
+
+/// A builder for [`Context`] that can attach both a [`Waker`] and a
+/// [`LocalWaker`] to the same context.
+///
+/// This wraps std's own `ContextBuilder`, which relies on [`LocalWaker`]
+/// (currently gated behind the unstable `local_waker` feature), so that
+/// callers of this crate only need to opt into `wakeful`'s `nightly` feature
+/// rather than the std feature directly. It's most useful for driving
+/// futures that may poll either [`Context::waker`] or
+/// [`Context::local_waker`], such as inside
+/// [`Blocking::blocking_wait_with_local`](crate::Blocking::blocking_wait_with_local).
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use wakeful::{ContextBuilder, LocalWake, Wake};
+///
+/// let waker = thread::current().into_waker();
+/// let local_waker = thread::current().into_local_waker();
+/// let context = ContextBuilder::from_waker(&waker)
+///     .local_waker(&local_waker)
+///     .build();
+/// ```
+pub struct ContextBuilder<'a>(std::task::ContextBuilder<'a>);
+
+impl<'a> ContextBuilder<'a> {
+    /// Create a new builder from a [`Waker`].
+    pub fn from_waker(waker: &'a Waker) -> Self {
+        Self(std::task::ContextBuilder::from_waker(waker))
+    }
+
+    /// Attach a [`LocalWaker`] to the context being built.
+    pub fn local_waker(self, local_waker: &'a LocalWaker) -> Self {
+        Self(self.0.local_waker(local_waker))
+    }
+
+    /// Finish building and produce the [`Context`].
+    pub fn build(self) -> Context<'a> {
+        self.0.build()
+    }
+}
+
+impl fmt::Debug for ContextBuilder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextBuilder").finish_non_exhaustive()
+    }
+}
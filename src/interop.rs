@@ -0,0 +1,155 @@
+use std::{fmt, sync::Arc};
+
+use crate::Wake;
+
+/// Adapts any type implementing the standard library's `Arc`-based
+/// [`std::task::Wake`] trait into this crate's [`Wake`] trait.
+///
+/// The value is wrapped in an `Arc`, which is already pointer-sized, so it
+/// lands on [`Wake::into_waker`]'s thin-pointer path with no extra
+/// allocation. This lets code written against std's `Wake` plug into APIs
+/// that expect this crate's `Wake` instead of rewriting it.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use wakeful::{StdWake, Wake};
+///
+/// struct MyWaker;
+///
+/// impl std::task::Wake for MyWaker {
+///     fn wake(self: Arc<Self>) {
+///         println!("woken!");
+///     }
+/// }
+///
+/// let waker = StdWake::from(Arc::new(MyWaker)).into_waker();
+/// waker.wake(); // prints "woken!"
+/// ```
+pub struct StdWake<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized> Clone for StdWake<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for StdWake<T> {
+    fn from(wake: Arc<T>) -> Self {
+        Self(wake)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for StdWake<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StdWake").finish_non_exhaustive()
+    }
+}
+
+impl<T> Wake for StdWake<T>
+where
+    T: std::task::Wake + Send + Sync + 'static,
+{
+    fn wake(self) {
+        std::task::Wake::wake(self.0);
+    }
+
+    fn wake_by_ref(&self) {
+        std::task::Wake::wake_by_ref(&self.0);
+    }
+}
+
+/// Adapts a type implementing this crate's [`Wake`] trait into the standard
+/// library's `Arc`-based [`std::task::Wake`] trait.
+///
+/// This is the inverse of [`StdWake`], useful for handing a [`Wake`]
+/// implementation to an API that expects the std trait instead, such as
+/// `Waker::from(Arc<W>)`.
+///
+/// # Examples
+///
+/// ```
+/// use std::{sync::Arc, task::Waker};
+/// use wakeful::{ToStd, Wake};
+///
+/// #[derive(Clone)]
+/// struct MyWaker;
+///
+/// impl Wake for MyWaker {
+///     fn wake_by_ref(&self) {
+///         println!("woken!");
+///     }
+/// }
+///
+/// let waker = Waker::from(Arc::new(ToStd::from(MyWaker)));
+/// waker.wake(); // prints "woken!"
+/// ```
+#[derive(Clone)]
+pub struct ToStd<W>(W);
+
+impl<W> From<W> for ToStd<W> {
+    fn from(wake: W) -> Self {
+        Self(wake)
+    }
+}
+
+impl<W> fmt::Debug for ToStd<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToStd").finish_non_exhaustive()
+    }
+}
+
+impl<W: Wake> std::task::Wake for ToStd<W> {
+    fn wake(self: Arc<Self>) {
+        match Arc::try_unwrap(self) {
+            Ok(this) => this.0.wake(),
+            Err(shared) => shared.0.wake_by_ref(),
+        }
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.wake_by_ref();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn std_wake_forwards_to_wrapped_std_wake() {
+        struct Impl(AtomicUsize);
+
+        impl std::task::Wake for Impl {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let inner = Arc::new(Impl(AtomicUsize::new(0)));
+        let waker = StdWake::from(inner.clone()).into_waker();
+
+        waker.wake_by_ref();
+        assert_eq!(inner.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn to_std_forwards_to_wrapped_wake() {
+        #[derive(Clone)]
+        struct Impl(Arc<AtomicUsize>);
+
+        impl Wake for Impl {
+            fn wake_by_ref(&self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let woke = Arc::new(AtomicUsize::new(0));
+        let waker = std::task::Waker::from(Arc::new(ToStd::from(Impl(woke.clone()))));
+
+        waker.wake_by_ref();
+        assert_eq!(woke.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "nightly")]
+use std::task::LocalWaker;
 use std::{
     mem,
     ptr,
@@ -25,35 +27,116 @@ pub fn waker_fn(f: impl Fn() + Send + Sync + 'static) -> Waker {
     Impl(Arc::new(f)).into_waker()
 }
 
-/// Helper trait that makes it easier to implement wakers.
+/// Create a [`Waker`] that does nothing when woken.
+///
+/// This is handy for polling a future once without caring whether it gets
+/// rescheduled, such as in tests, or as a placeholder before a real executor
+/// is attached. Since the underlying type is zero-sized, [`Wake::into_waker`]
+/// uses its thin-pointer optimization, so this allocates nothing and clones
+/// are free.
+///
+/// # Examples
+///
+/// ```
+/// let waker = wakeful::noop();
+/// waker.wake(); // does nothing
+/// ```
+pub fn noop() -> Waker {
+    #[derive(Clone)]
+    struct Impl;
+
+    impl Wake for Impl {
+        fn wake_by_ref(&self) {}
+    }
+
+    Impl.into_waker()
+}
+
+/// Zero-cost helper trait that makes it easier to implement wakers.
 ///
 /// Implementing this trait provides you with [`Wake::into_waker`], which allows
-/// you to construct a [`Waker`] from any type implementing [`Wake`].
+/// you to construct a [`Waker`] from any type implementing [`Wake`]. The only
+/// method you must implement is [`Wake::wake_by_ref`] which can encapsulate all
+/// your custom wake-up behavior.
+///
+/// Your custom wakers must also implement [`Clone`], [`Send`], and [`Sync`] to
+/// comply with the contract of [`Waker`]. You are free to choose any strategy
+/// you like to handle cloning; bundling your state in an inner [`Arc`](std::sync::Arc) is
+/// common and plays nicely with this trait.
+///
+/// # Provided implementations
+///
+/// A simple waker implementation is provided for [`std::thread::Thread`], which
+/// merely calls `unpark()`. This almost trivializes implementing your own
+/// single-threaded `block_on` executor. An example of this is shown in the
+/// crate-level documentation.
+///
+/// # Optimizations
 ///
 /// If the size of `Self` is less than or equal to pointer size, as an
 /// optimization the underlying implementation will pass `self` in directly to
-/// `RawWakerVTable` functions. For types larger than a pointer, an allocation
+/// [`RawWakerVTable`] functions. For types larger than a pointer, an allocation
 /// will be made on creation and when cloning.
+///
+/// # Examples
+///
+/// ```
+/// use wakeful::Wake;
+///
+/// /// Doesn't actually do anything except print a message when wake is called.
+/// #[derive(Clone)]
+/// struct PrintWaker;
+///
+/// impl Wake for PrintWaker {
+///     fn wake_by_ref(&self) {
+///         println!("wake called!");
+///     }
+/// }
+///
+/// let waker = PrintWaker.into_waker();
+/// waker.wake(); // prints "wake called!"
+/// ```
+///
+/// ```
+/// use std::task::Waker;
+/// use wakeful::Wake;
+///
+/// /// Delegates wake calls to multiple wakers.
+/// #[derive(Clone)]
+/// struct MultiWaker(Vec<Waker>);
+///
+/// impl Wake for MultiWaker {
+///     fn wake(self) {
+///         for waker in self.0 {
+///             waker.wake();
+///         }
+///     }
+///
+///     fn wake_by_ref(&self) {
+///         for waker in &self.0 {
+///             waker.wake_by_ref();
+///         }
+///     }
+/// }
+/// ```
 pub trait Wake: Send + Sync + Clone {
-    /// Wake up the task associated with this waker, consuming the waker.
+    /// Wake up the task associated with this waker, consuming the waker. When
+    /// converted into a waker handle, this method is invoked whenever
+    /// [`Waker::wake`] is called.
     ///
     /// By default, this delegates to [`Wake::wake_by_ref`], but can be
-    /// overridden if a more efficient implementation is possible.
+    /// overridden if a more efficient owned implementation is possible.
     fn wake(self) {
         self.wake_by_ref();
     }
 
-    /// Wake up the task associated with this waker, consuming the waker.
+    /// Wake up the task associated with this waker, without consuming the
+    /// waker. When converted into a waker handle, this method is invoked
+    /// whenever [`Waker::wake_by_ref`] is called.
     fn wake_by_ref(&self);
 
     /// Convert this into a [`Waker`] handle.
     fn into_waker(self) -> Waker {
-        unsafe { Waker::from_raw(self.into_raw_waker()) }
-    }
-
-    /// Convert this into a [`RawWaker`] handle.
-    #[inline]
-    fn into_raw_waker(self) -> RawWaker {
         // There's a fair bit of magic going on here, so watch out. There are
         // two possible implementations for this function, and which one we
         // invoke is decided at compile time based on the memory size of `Self`.
@@ -73,12 +156,21 @@ pub trait Wake: Send + Sync + Clone {
         // essentially being passed around directly with no indirection without
         // any extra effort from the implementer.
 
+        /// Convert a wake into a [`RawWaker`] handle.
+        fn create_raw_waker<W: Wake>(wake: W) -> RawWaker {
+            if mem::size_of::<W>() <= mem::size_of::<*const ()>() {
+                create_thin(wake)
+            } else {
+                create_boxed(wake)
+            }
+        }
+
         /// Convert a wake into a [`RawWaker`] handle by allocating a box.
-        fn into_boxed<W: Wake>(wake: W) -> RawWaker {
+        fn create_boxed<W: Wake>(wake: W) -> RawWaker {
             RawWaker::new(
                 Box::into_raw(Box::new(wake)) as *const (),
                 &RawWakerVTable::new(
-                    |data| unsafe { (&*(data as *const W)).clone().into_raw_waker() },
+                    |data| unsafe { create_raw_waker((&*(data as *const W)).clone()) },
                     |data| unsafe {
                         Box::from_raw(data as *mut W).wake();
                     },
@@ -94,8 +186,8 @@ pub trait Wake: Send + Sync + Clone {
 
         /// Convert a wake into a [`RawWaker`] handle by transmuting into a data
         /// pointer.
-        fn into_thin<W: Wake>(wake: W) -> RawWaker {
-            let mut data = std::ptr::null();
+        fn create_thin<W: Wake>(wake: W) -> RawWaker {
+            let mut data = ptr::null();
 
             // The following code will unleash the kraken if this invariant
             // isn't upheld.
@@ -121,9 +213,7 @@ pub trait Wake: Send + Sync + Clone {
                 data,
                 &RawWakerVTable::new(
                     |data| unsafe {
-                        (&*(&data as *const *const () as *const W))
-                            .clone()
-                            .into_raw_waker()
+                        create_raw_waker((&*(&data as *const *const () as *const W)).clone())
                     },
                     |data| unsafe {
                         mem::transmute_copy::<_, W>(&data).wake();
@@ -138,11 +228,7 @@ pub trait Wake: Send + Sync + Clone {
             )
         }
 
-        if mem::size_of::<Self>() <= mem::size_of::<*const ()>() {
-            into_thin(self)
-        } else {
-            into_boxed(self)
-        }
+        unsafe { Waker::from_raw(create_raw_waker(self)) }
     }
 }
 
@@ -152,6 +238,119 @@ impl Wake for std::thread::Thread {
     }
 }
 
+/// Helper trait that makes it easier to implement single-threaded, `!Send`
+/// wakers.
+///
+/// This is a sibling of [`Wake`] for situations where your waker can't (or
+/// shouldn't have to) be [`Send`] and [`Sync`], such as when it holds an
+/// `Rc` or a `Cell`. Implementing this trait provides you with
+/// [`LocalWake::into_local_waker`], which allows you to construct a
+/// [`LocalWaker`] from any type implementing [`LocalWake`].
+///
+/// # Optimizations
+///
+/// The same size-based optimization used by [`Wake::into_waker`] applies
+/// here: if the size of `Self` is less than or equal to pointer size, the
+/// underlying implementation will pass `self` in directly to
+/// `RawWakerVTable` functions. For types larger than a pointer, an
+/// allocation will be made on creation and when cloning.
+#[cfg(feature = "nightly")]
+pub trait LocalWake: Clone {
+    /// Wake up the task associated with this waker, consuming the waker.
+    ///
+    /// By default, this delegates to [`LocalWake::wake_by_ref`], but can be
+    /// overridden if a more efficient implementation is possible.
+    fn wake(self) {
+        self.wake_by_ref();
+    }
+
+    /// Wake up the task associated with this waker, without consuming the
+    /// waker.
+    fn wake_by_ref(&self);
+
+    /// Convert this into a [`LocalWaker`] handle.
+    fn into_local_waker(self) -> LocalWaker {
+        // Same two strategies as `Wake::into_waker`, just without the
+        // `Send + Sync` bound, which is what makes it sound for the vtable
+        // closures to touch non-atomic state like `Rc` and `Cell`.
+
+        /// Convert a wake into a [`RawWaker`] handle.
+        fn create_local_raw_waker<W: LocalWake>(wake: W) -> RawWaker {
+            if mem::size_of::<W>() <= mem::size_of::<*const ()>() {
+                create_local_thin(wake)
+            } else {
+                create_local_boxed(wake)
+            }
+        }
+
+        /// Convert a wake into a [`RawWaker`] handle by allocating a box.
+        fn create_local_boxed<W: LocalWake>(wake: W) -> RawWaker {
+            RawWaker::new(
+                Box::into_raw(Box::new(wake)) as *const (),
+                &RawWakerVTable::new(
+                    |data| unsafe { create_local_raw_waker((&*(data as *const W)).clone()) },
+                    |data| unsafe {
+                        Box::from_raw(data as *mut W).wake();
+                    },
+                    |data| unsafe {
+                        (&*(data as *const W)).wake_by_ref();
+                    },
+                    |data| unsafe {
+                        Box::from_raw(data as *mut W);
+                    },
+                ),
+            )
+        }
+
+        /// Convert a wake into a [`RawWaker`] handle by transmuting into a data
+        /// pointer.
+        fn create_local_thin<W: LocalWake>(wake: W) -> RawWaker {
+            let mut data = ptr::null();
+
+            // The following code will unleash the kraken if this invariant
+            // isn't upheld.
+            debug_assert!(mem::size_of::<W>() <= mem::size_of_val(&data));
+
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    &wake as *const W,
+                    &mut data as *mut *const () as *mut W,
+                    1,
+                );
+            }
+
+            mem::forget(wake);
+
+            RawWaker::new(
+                data,
+                &RawWakerVTable::new(
+                    |data| unsafe {
+                        create_local_raw_waker((&*(&data as *const *const () as *const W)).clone())
+                    },
+                    |data| unsafe {
+                        mem::transmute_copy::<_, W>(&data).wake();
+                    },
+                    |data| unsafe {
+                        (&*(&data as *const *const () as *const W)).wake_by_ref();
+                    },
+                    |data| unsafe {
+                        mem::transmute_copy::<_, W>(&data);
+                    },
+                ),
+            )
+        }
+
+        unsafe { LocalWaker::from_raw(create_local_raw_waker(self)) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl LocalWake for std::thread::Thread {
+    fn wake_by_ref(&self) {
+        self.unpark();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +380,13 @@ mod tests {
         assert_eq!(WOKE.load(Ordering::SeqCst), 2);
     }
 
+    #[test]
+    fn noop_does_nothing() {
+        let waker = noop();
+        waker.wake_by_ref();
+        waker.clone().wake();
+    }
+
     #[test]
     fn ptr_sized_impl() {
         #[derive(Clone, Default)]
@@ -222,4 +428,28 @@ mod tests {
         waker.clone().wake();
         assert_eq!(woke.load(Ordering::SeqCst), 2);
     }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn local_wake_ptr_sized_impl() {
+        use std::{cell::Cell, rc::Rc};
+
+        #[derive(Clone)]
+        struct Impl(Rc<Cell<usize>>);
+
+        impl LocalWake for Impl {
+            fn wake_by_ref(&self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let woke = Rc::new(Cell::new(0));
+
+        let waker = Impl(woke.clone()).into_local_waker();
+        waker.wake_by_ref();
+        assert_eq!(woke.get(), 1);
+
+        waker.clone().wake();
+        assert_eq!(woke.get(), 2);
+    }
 }